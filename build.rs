@@ -0,0 +1,39 @@
+//! Generates the known UF2 family ID table from `family_ids.tsv` at build
+//! time, so the list is easy to extend without touching code.
+
+use std::{env, fmt::Write as _, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let data_path = Path::new(&manifest_dir).join("family_ids.tsv");
+
+    println!("cargo:rerun-if-changed={}", data_path.display());
+
+    let data = fs::read_to_string(&data_path).expect("read family_ids.tsv");
+
+    let mut table = String::from(
+        "// Generated by build.rs from `family_ids.tsv`. Do not edit by hand.\n\
+         pub(crate) const FAMILY_IDS: &[(&str, u32)] = &[\n",
+    );
+
+    for line in data.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split('\t');
+        let name = fields.next().expect("name column");
+        let id = fields.next().expect("id column");
+        let id = u32::from_str_radix(id.trim_start_matches("0x"), 16)
+            .expect("hex family id");
+
+        writeln!(table, "    ({name:?}, 0x{id:08x}),").unwrap();
+    }
+
+    table.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("family_ids.rs"), table)
+        .expect("write family_ids.rs");
+}
@@ -0,0 +1,140 @@
+use anyhow::Error;
+use clap::{Args, Parser, Subcommand};
+use std::{
+    fs,
+    io::{BufReader, BufWriter},
+    path::{Component, Path, PathBuf},
+};
+use uftwo::{read_container, write_container, ContainerFile};
+
+/// Default number of payload bytes per block.
+const PAYLOAD_SIZE: usize = 256;
+
+#[derive(Parser)]
+pub struct Cmd {
+    #[clap(subcommand)]
+    action: Action,
+}
+
+#[derive(Subcommand)]
+enum Action {
+    /// Pack multiple files into a single UF2 container.
+    Pack(PackCmd),
+    /// Extract files from a UF2 container.
+    Extract(ExtractCmd),
+}
+
+#[derive(Args)]
+struct PackCmd {
+    #[arg(value_name = "OUTPUT")]
+    output_path: PathBuf,
+    /// `path@address` pairs, one per input file. Address may be hex
+    /// (`0x...`) or decimal.
+    #[arg(value_name = "FILE@ADDRESS", required = true)]
+    inputs: Vec<String>,
+}
+
+#[derive(Args)]
+struct ExtractCmd {
+    #[arg(value_name = "INPUT")]
+    input_path: PathBuf,
+    /// Directory to extract files into.
+    #[arg(value_name = "OUTPUT_DIR")]
+    output_dir: PathBuf,
+}
+
+impl Cmd {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self.action {
+            Action::Pack(cmd) => pack(cmd),
+            Action::Extract(cmd) => extract(cmd),
+        }
+    }
+}
+
+fn pack(cmd: PackCmd) -> anyhow::Result<()> {
+    let mut paths = Vec::new();
+    let mut addrs = Vec::new();
+    let mut datas = Vec::new();
+
+    for input in &cmd.inputs {
+        let (path, addr) = input.rsplit_once('@').ok_or_else(|| {
+            Error::msg(format!("expected FILE@ADDRESS, got {input:?}"))
+        })?;
+
+        let addr = match addr.strip_prefix("0x").or_else(|| addr.strip_prefix("0X")) {
+            Some(hex) => u32::from_str_radix(hex, 16)?,
+            None => addr.parse::<u32>()?,
+        };
+
+        paths.push(path.to_string());
+        addrs.push(addr);
+        datas.push(fs::read(path)?);
+    }
+
+    let files: Vec<ContainerFile> = paths
+        .iter()
+        .zip(&addrs)
+        .zip(&datas)
+        .map(|((path, &target_addr), data)| ContainerFile {
+            path,
+            target_addr,
+            data,
+        })
+        .collect();
+
+    let output = BufWriter::new(fs::File::create(&cmd.output_path)?);
+    write_container(output, &files, PAYLOAD_SIZE).map_err(Error::msg)?;
+
+    println!("Packed {} files into {:?}", files.len(), cmd.output_path);
+
+    Ok(())
+}
+
+fn extract(cmd: ExtractCmd) -> anyhow::Result<()> {
+    let input = BufReader::new(fs::File::open(&cmd.input_path)?);
+    let files = read_container(input).map_err(Error::msg)?;
+
+    fs::create_dir_all(&cmd.output_dir)?;
+
+    for file in &files {
+        let relative = sanitize_entry_path(&file.path)?;
+        let out_path = cmd.output_dir.join(relative);
+
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&out_path, &file.data)?;
+        println!("Extracted {:?} ({} bytes)", out_path, file.data.len());
+    }
+
+    Ok(())
+}
+
+/// Validate a `FilePath` extension value recovered from a (possibly
+/// untrusted) container image before it is joined onto an output directory.
+///
+/// Rejects absolute paths and any `..`/`.` components, which `PathBuf::join`
+/// does not guard against on its own, to prevent a crafted container from
+/// writing outside the extraction directory.
+fn sanitize_entry_path(path: &str) -> anyhow::Result<PathBuf> {
+    let mut sanitized = PathBuf::new();
+
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(part) => sanitized.push(part),
+            _ => {
+                return Err(Error::msg(format!(
+                    "refusing to extract unsafe container path {path:?}"
+                )))
+            }
+        }
+    }
+
+    if sanitized.as_os_str().is_empty() {
+        return Err(Error::msg("refusing to extract empty container path"));
+    }
+
+    Ok(sanitized)
+}
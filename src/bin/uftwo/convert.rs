@@ -3,12 +3,25 @@ use clap::Parser;
 use clap_num::maybe_hex;
 use std::{
     ffi::OsStr,
-    fs::File,
-    io::{Read, Write},
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
     path::PathBuf,
 };
-use uftwo::{Block, Flags};
-use zerocopy::AsBytes;
+#[cfg(feature = "sha2")]
+use sha2::{Digest, Sha256};
+use uftwo::{FamilyId, Uf2Reader, Uf2Writer};
+#[cfg(feature = "sha2")]
+use uftwo::ExtensionTag;
+
+/// Parse a family ID given as a well-known board/MCU name (case-insensitive)
+/// or as a hex/decimal number.
+fn parse_family_id(s: &str) -> Result<FamilyId, String> {
+    if let Some(id) = FamilyId::from_name(s) {
+        return Ok(id);
+    }
+
+    maybe_hex::<u32>(s).map(FamilyId::from_id).map_err(|e| e.to_string())
+}
 
 #[derive(Parser)]
 pub struct Cmd {
@@ -19,9 +32,20 @@ pub struct Cmd {
     /// Target address in flash memory.
     #[clap(long, value_parser=maybe_hex::<u32>)]
     target_addr: u32,
-    /// Family ID.
+    /// Family ID, as a well-known name (e.g. `rp2040`) or a hex/decimal
+    /// number.
+    #[clap(long, value_parser = parse_family_id)]
+    family_id: Option<FamilyId>,
+    /// Compute and embed an MD5 checksum in each block, and verify it when
+    /// reading back a UF2 file.
+    #[cfg(feature = "md5")]
     #[clap(long)]
-    family_id: Option<u32>,
+    checksum: bool,
+    /// Compute and embed a SHA-256 digest of the whole firmware image, and
+    /// verify it when reading back a UF2 file.
+    #[cfg(feature = "sha2")]
+    #[clap(long)]
+    sha2: bool,
 }
 
 impl Cmd {
@@ -54,13 +78,24 @@ impl Cmd {
         println!("Converting {:?} to {:?}", self.input_path, output_path);
 
         if input_uf2 {
-            uf2_to_bin(self.input_path, output_path)
+            uf2_to_bin(
+                self.input_path,
+                output_path,
+                #[cfg(feature = "md5")]
+                self.checksum,
+                #[cfg(feature = "sha2")]
+                self.sha2,
+            )
         } else {
             bin_to_uf2(
                 self.input_path,
                 output_path,
                 self.target_addr,
                 self.family_id,
+                #[cfg(feature = "md5")]
+                self.checksum,
+                #[cfg(feature = "sha2")]
+                self.sha2,
             )
         }
     }
@@ -71,78 +106,140 @@ fn bin_to_uf2(
     input: PathBuf,
     output: PathBuf,
     target_addr: u32,
-    family_id: Option<u32>,
+    family_id: Option<FamilyId>,
+    #[cfg(feature = "md5")] checksum: bool,
+    #[cfg(feature = "sha2")] sha2: bool,
 ) -> anyhow::Result<()> {
-    let mut input_file = File::open(input)?;
-    let mut output_file = File::create(output)?;
+    let input_len = fs::metadata(&input)?.len() as usize;
+    let mut reader = BufReader::new(File::open(input)?);
+    let writer = BufWriter::new(File::create(output)?);
+
+    let mut uf2 = Uf2Writer::new(
+        writer,
+        target_addr,
+        family_id.map(|f| f.id()),
+        input_len,
+    );
 
-    let mut binary = Vec::new();
-    input_file.read_to_end(&mut binary)?;
+    #[cfg(feature = "sha2")]
+    let mut hasher = Sha256::new();
 
-    let total_blocks = binary.chunks(256).count();
+    let mut buf = vec![0u8; uf2.payload_size()];
+    let mut total_bytes = 0usize;
 
-    binary.chunks(256).enumerate().for_each(|(index, chunk)| {
-        let mut block = Block::default();
+    loop {
+        let mut filled = 0;
+        while filled < buf.len() {
+            let read = reader.read(&mut buf[filled..])?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+        }
 
-        block.data_len = chunk.len() as u32;
-        block.target_addr = target_addr as u32;
+        if filled == 0 {
+            break;
+        }
 
-        if let Some(family_id) = family_id {
-            block.board_family_id_or_file_size = family_id;
-            block.flags = Flags::FamilyId;
+        let chunk = &buf[0..filled];
+
+        #[cfg(feature = "sha2")]
+        if sha2 {
+            hasher.update(chunk);
         }
 
-        block.block = index as u32;
-        block.total_blocks = total_blocks as u32;
+        let is_last = uf2.is_last_chunk();
+        let mut block = uf2.make_block(chunk).map_err(Error::msg)?;
 
-        block.data[0..chunk.len()].copy_from_slice(chunk);
+        #[cfg(feature = "md5")]
+        if checksum {
+            block.fill_checksum().map_err(Error::msg)?;
+        }
 
-        output_file.write(block.as_bytes()).unwrap();
-    });
+        #[cfg(feature = "sha2")]
+        if sha2 && is_last {
+            let digest: [u8; 32] = hasher.clone().finalize().into();
+            block
+                .push_extension(u32::from(ExtensionTag::Sha2Checksum), &digest)
+                .map_err(Error::msg)?;
+        }
+
+        uf2.write_block(&block).map_err(Error::msg)?;
+
+        total_bytes += chunk.len();
+
+        if filled < buf.len() {
+            break;
+        }
+    }
+
+    let total_blocks = uf2.total_blocks();
+    let mut writer = uf2.finish().map_err(Error::msg)?;
+    writer.flush()?;
 
     println!(
         "Written {} bytes into {} blocks.",
-        binary.len(),
-        total_blocks
+        total_bytes, total_blocks
     );
 
-    output_file.flush()?;
-
     Ok(())
 }
 
 /// UF2 to binary.
-fn uf2_to_bin(input: PathBuf, output: PathBuf) -> anyhow::Result<()> {
-    let mut input_file = File::open(input)?;
-    let mut output_file = File::create(output)?;
-
-    let mut binary: Vec<u8> = vec![];
+fn uf2_to_bin(
+    input: PathBuf,
+    output: PathBuf,
+    #[cfg(feature = "md5")] checksum: bool,
+    #[cfg(feature = "sha2")] sha2: bool,
+) -> anyhow::Result<()> {
+    let reader = BufReader::new(File::open(input)?);
+    let mut writer = BufWriter::new(File::create(output)?);
 
     println!("Reading blocks.");
 
-    let mut total_blocks = 0;
+    let mut total_blocks = 0usize;
+    let mut total_bytes = 0usize;
 
-    loop {
-        let mut buf = [0; 512];
+    #[cfg(feature = "sha2")]
+    let mut blocks = Vec::new();
 
-        let bytes = input_file.read(&mut buf)?;
+    for block in Uf2Reader::new(reader) {
+        let block = block.map_err(Error::msg)?;
 
-        if bytes < 512 {
-            break;
+        #[cfg(feature = "md5")]
+        if checksum && block.has_checksum() && !block.verify_checksum() {
+            return Err(Error::msg(format!(
+                "checksum mismatch in block {}",
+                block.block
+            )));
         }
 
-        let block = Block::from_bytes(&buf).map_err(Error::msg)?;
-
-        binary.extend(&buf[0..(block.data_len as usize)]);
+        let data = &block.data[0..block.data_len as usize];
+        writer.write_all(data)?;
 
+        total_bytes += data.len();
         total_blocks += 1;
+
+        #[cfg(feature = "sha2")]
+        if sha2 {
+            blocks.push(block);
+        }
     }
 
-    output_file.write(&binary)?;
+    #[cfg(feature = "sha2")]
+    if sha2 {
+        match uftwo::verify_firmware_digest(&blocks) {
+            Some(true) => {}
+            Some(false) => return Err(Error::msg("SHA-256 digest mismatch")),
+            None => {
+                return Err(Error::msg("no SHA-256 digest found in image"))
+            }
+        }
+    }
 
-    println!("Read {} bytes from {} blocks.", binary.len(), total_blocks);
+    writer.flush()?;
 
-    output_file.flush()?;
+    println!("Read {} bytes from {} blocks.", total_bytes, total_blocks);
 
     Ok(())
 }
@@ -0,0 +1,162 @@
+use anyhow::Error;
+use clap::Parser;
+use std::{collections::HashSet, fs::File, io::BufReader, path::PathBuf};
+use uftwo::{Block, ExtensionTag, FamilyId, Flags, Uf2Reader};
+
+#[derive(Parser)]
+pub struct Cmd {
+    #[arg(value_name = "INPUT")]
+    input_path: PathBuf,
+}
+
+impl Cmd {
+    pub fn run(self) -> anyhow::Result<()> {
+        let reader = BufReader::new(File::open(&self.input_path)?);
+
+        let mut blocks = Vec::new();
+        for block in Uf2Reader::new(reader) {
+            blocks.push(block.map_err(Error::msg)?);
+        }
+
+        let Some(first) = blocks.first() else {
+            println!("No blocks found.");
+            return Ok(());
+        };
+
+        println!("Blocks: {}", blocks.len());
+
+        report_totals(&blocks);
+        report_segments(&blocks);
+        report_family_id(&blocks);
+        println!("Flags: {:?}", first.flags);
+        report_extensions(&blocks);
+        report_checksums(&blocks);
+
+        Ok(())
+    }
+}
+
+/// Check `total_blocks` agreement and duplicate block indices.
+fn report_totals(blocks: &[Block]) {
+    let declared_total = blocks[0].total_blocks;
+
+    if blocks.iter().any(|b| b.total_blocks != declared_total) {
+        println!("Anomaly: blocks disagree on total_blocks.");
+    } else if declared_total as usize != blocks.len() {
+        println!(
+            "Anomaly: total_blocks ({}) does not match block count read ({}).",
+            declared_total,
+            blocks.len()
+        );
+    }
+
+    let mut seen = HashSet::new();
+    for block in blocks {
+        if !seen.insert(block.block) {
+            println!("Anomaly: duplicate block index {}.", block.block);
+        }
+    }
+}
+
+/// `start + len`, reporting an overflow anomaly and falling back to `start`
+/// instead of panicking or wrapping if the addition doesn't fit in a `u32`.
+fn checked_segment_end(start: u32, len: u32) -> u32 {
+    start.checked_add(len).unwrap_or_else(|| {
+        println!(
+            "Anomaly: block at 0x{start:08x} with length {len} overflows the address space."
+        );
+        start
+    })
+}
+
+/// Print contiguous `target_addr` runs, flagging gaps and overlaps.
+fn report_segments(blocks: &[Block]) {
+    println!("Segments:");
+
+    let mut sorted: Vec<&Block> = blocks.iter().collect();
+    sorted.sort_by_key(|b| b.target_addr);
+
+    let mut start = sorted[0].target_addr;
+    let mut end = checked_segment_end(start, sorted[0].data_len);
+
+    for block in &sorted[1..] {
+        if block.target_addr == end {
+            end = checked_segment_end(end, block.data_len);
+            continue;
+        }
+
+        println!("  0x{start:08x}..0x{end:08x}");
+
+        if block.target_addr < end {
+            println!(
+                "Anomaly: overlapping blocks around 0x{:08x}.",
+                block.target_addr
+            );
+        } else {
+            println!(
+                "Anomaly: gap in address map between 0x{end:08x} and 0x{:08x}.",
+                block.target_addr
+            );
+        }
+
+        start = block.target_addr;
+        end = checked_segment_end(start, block.data_len);
+    }
+
+    println!("  0x{start:08x}..0x{end:08x}");
+}
+
+fn report_family_id(blocks: &[Block]) {
+    match blocks.iter().find(|b| b.flags.contains(Flags::FamilyId)) {
+        Some(block) => {
+            let family = FamilyId::from_id(block.board_family_id_or_file_size);
+            println!("Family ID: {family}");
+        }
+        None => println!("Family ID: none"),
+    }
+}
+
+fn report_extensions(blocks: &[Block]) {
+    for block in blocks {
+        let Some(extensions) = block.extensions() else {
+            continue;
+        };
+
+        for extension in extensions {
+            let value = match extension.tag {
+                ExtensionTag::SemverString | ExtensionTag::DescriptionString => {
+                    core::str::from_utf8(extension.data)
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|_| format!("{:02x?}", extension.data))
+                }
+                _ => format!("{:02x?}", extension.data),
+            };
+
+            println!(
+                "Extension in block {}: {:?} = {value}",
+                block.block, extension.tag
+            );
+        }
+    }
+}
+
+fn report_checksums(blocks: &[Block]) {
+    #[cfg(feature = "md5")]
+    for block in blocks {
+        if block.has_checksum() {
+            let ok = block.verify_checksum();
+            println!(
+                "MD5 checksum in block {}: {}",
+                block.block,
+                if ok { "valid" } else { "INVALID" }
+            );
+        }
+    }
+
+    #[cfg(feature = "sha2")]
+    match uftwo::verify_firmware_digest(blocks) {
+        Some(true) => println!("SHA-256 digest: valid"),
+        Some(false) => println!("SHA-256 digest: INVALID"),
+        None => {}
+    }
+}
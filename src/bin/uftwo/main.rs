@@ -1,6 +1,8 @@
 //! uftwo CLI tool.
 
+mod container;
 mod convert;
+mod inspect;
 
 use clap::Parser;
 
@@ -15,6 +17,10 @@ struct Cli {
 enum Subcommand {
     /// Convert a binary or file to a UF2 file.
     Convert(convert::Cmd),
+    /// Parse a UF2 file and report its structure.
+    Inspect(inspect::Cmd),
+    /// Pack or extract a multi-file UF2 container.
+    Container(container::Cmd),
 }
 
 fn main() -> anyhow::Result<()> {
@@ -22,5 +28,7 @@ fn main() -> anyhow::Result<()> {
 
     match args.subcommand {
         Subcommand::Convert(cmd) => cmd.run(),
+        Subcommand::Inspect(cmd) => cmd.run(),
+        Subcommand::Container(cmd) => cmd.run(),
     }
 }
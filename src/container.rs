@@ -0,0 +1,112 @@
+//! Multi-file UF2 containers ([`Flags::FileContainer`]).
+//!
+//! A container image packs several files into one `.uf2`: each block's
+//! `board_family_id_or_file_size` carries its file's total size, and an
+//! [`ExtensionTag::FilePath`] extension on every block names which file it
+//! belongs to.
+
+use crate::{Block, BlockError, ExtensionTag, Flags, Uf2Reader};
+use std::io::{Read, Write};
+
+/// One file to be packed into a multi-file UF2 container.
+pub struct ContainerFile<'a> {
+    /// Path recorded in the image, used to name the file on extraction.
+    pub path: &'a str,
+    /// Base flash address for this file's blocks.
+    pub target_addr: u32,
+    /// File contents.
+    pub data: &'a [u8],
+}
+
+/// Write `files` as a single [`Flags::FileContainer`] UF2 image, chunked at
+/// `payload_size` bytes per block.
+pub fn write_container<W: Write>(
+    mut writer: W,
+    files: &[ContainerFile],
+    payload_size: usize,
+) -> Result<(), BlockError> {
+    // `.max(1)` so a zero-length file still gets a single (empty) block,
+    // instead of being silently dropped from the container.
+    let chunk_counts: Vec<usize> = files
+        .iter()
+        .map(|f| f.data.len().div_ceil(payload_size).max(1))
+        .collect();
+    let total_blocks: usize = chunk_counts.iter().sum();
+
+    let mut block_index = 0;
+
+    for (file, &chunk_count) in files.iter().zip(&chunk_counts) {
+        for i in 0..chunk_count {
+            let chunk_start = i * payload_size;
+            let chunk_end = (chunk_start + payload_size).min(file.data.len());
+            let chunk = &file.data[chunk_start..chunk_end];
+            let target_addr = (file.target_addr as usize)
+                .checked_add(chunk_start)
+                .filter(|addr| *addr <= u32::MAX as usize)
+                .ok_or(BlockError::AddressOverflow)?;
+
+            let mut block =
+                Block::new(block_index, total_blocks, chunk, target_addr);
+            block.data_len = chunk.len() as u32;
+            block.board_family_id_or_file_size = file.data.len() as u32;
+            block.flags |= Flags::FileContainer;
+
+            block.push_extension(
+                u32::from(ExtensionTag::FilePath),
+                file.path.as_bytes(),
+            )?;
+
+            writer.write_all(block.as_bytes()).map_err(|_| BlockError::Io)?;
+
+            block_index += 1;
+        }
+    }
+
+    writer.flush().map_err(|_| BlockError::Io)
+}
+
+/// A file recovered from a [`Flags::FileContainer`] UF2 image.
+pub struct ExtractedFile {
+    /// Path recorded in the image.
+    pub path: String,
+    /// Reassembled file contents.
+    pub data: Vec<u8>,
+}
+
+/// Split a [`Flags::FileContainer`] UF2 image into its constituent files,
+/// grouped by the path recorded in each block's [`ExtensionTag::FilePath`]
+/// extension.
+pub fn read_container<R: Read>(
+    reader: R,
+) -> Result<Vec<ExtractedFile>, BlockError> {
+    let mut files: Vec<ExtractedFile> = Vec::new();
+
+    for block in Uf2Reader::new(reader) {
+        let block = block?;
+
+        if !block.flags.contains(Flags::FileContainer) {
+            continue;
+        }
+
+        let path = block
+            .extensions()
+            .and_then(|mut exts| {
+                exts.find(|e| e.tag == ExtensionTag::FilePath)
+            })
+            .and_then(|ext| core::str::from_utf8(ext.data).ok())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let data = &block.data[0..block.data_len as usize];
+
+        match files.iter_mut().find(|f| f.path == path) {
+            Some(file) => file.data.extend_from_slice(data),
+            None => files.push(ExtractedFile {
+                path,
+                data: data.to_vec(),
+            }),
+        }
+    }
+
+    Ok(files)
+}
@@ -0,0 +1,73 @@
+//! Known UF2 family IDs.
+//!
+//! The `FAMILY_IDS` table is generated at build time from `family_ids.tsv`
+//! by `build.rs`.
+
+use core::fmt;
+
+include!(concat!(env!("OUT_DIR"), "/family_ids.rs"));
+
+/// A UF2 family ID, resolved to a well-known board/MCU name where possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FamilyId(u32);
+
+impl FamilyId {
+    /// Wrap a raw family ID value.
+    pub fn from_id(id: u32) -> Self {
+        Self(id)
+    }
+
+    /// Look up a well-known family ID by its case-insensitive name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        FAMILY_IDS
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, id)| Self(*id))
+    }
+
+    /// The raw family ID value.
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+
+    /// The well-known name for this family ID, if any.
+    pub fn name(&self) -> Option<&'static str> {
+        FAMILY_IDS
+            .iter()
+            .find(|(_, id)| *id == self.0)
+            .map(|(name, _)| *name)
+    }
+}
+
+impl From<u32> for FamilyId {
+    fn from(id: u32) -> Self {
+        Self::from_id(id)
+    }
+}
+
+impl fmt::Display for FamilyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{name} (0x{:08x})", self.0),
+            None => write!(f, "0x{:08x}", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_family_by_name() {
+        let id = FamilyId::from_name("rp2040").unwrap();
+        assert_eq!(id.id(), 0xe48bff56);
+        assert_eq!(id.name(), Some("RP2040"));
+    }
+
+    #[test]
+    fn unknown_family_id() {
+        let id = FamilyId::from_id(0xdeadbeef);
+        assert_eq!(id.name(), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! Streaming readers and writers built on [`std::io`].
+//!
+//! These complement the plain [`Block`](crate::Block) API for callers that
+//! want to convert large images without buffering the whole file in memory.
+
+use crate::{Block, BlockError, Flags};
+use std::io::{Read, Write};
+
+/// Incrementally writes firmware data to a UF2 image.
+///
+/// Each call to [`write_chunk`](Self::write_chunk) emits one [`Block`],
+/// addressed sequentially from the writer's base `target_addr`. The total
+/// number of blocks is fixed up front from the firmware length passed to
+/// [`new`](Self::new)/[`with_payload_size`](Self::with_payload_size), so
+/// memory use stays bounded to a single block regardless of image size.
+pub struct Uf2Writer<W> {
+    writer: W,
+    target_addr: u32,
+    family_id: Option<u32>,
+    payload_size: usize,
+    block: u32,
+    total_blocks: u32,
+}
+
+impl<W: Write> Uf2Writer<W> {
+    /// Default number of payload bytes per block.
+    pub const DEFAULT_PAYLOAD_SIZE: usize = 256;
+
+    /// Create a writer using [`Self::DEFAULT_PAYLOAD_SIZE`].
+    pub fn new(
+        writer: W,
+        target_addr: u32,
+        family_id: Option<u32>,
+        total_len: usize,
+    ) -> Self {
+        Self::with_payload_size(
+            writer,
+            target_addr,
+            family_id,
+            total_len,
+            Self::DEFAULT_PAYLOAD_SIZE,
+        )
+    }
+
+    /// Create a writer with a custom payload-per-block size.
+    pub fn with_payload_size(
+        writer: W,
+        target_addr: u32,
+        family_id: Option<u32>,
+        total_len: usize,
+        payload_size: usize,
+    ) -> Self {
+        let total_blocks = total_len.div_ceil(payload_size);
+
+        Self {
+            writer,
+            target_addr,
+            family_id,
+            payload_size,
+            block: 0,
+            total_blocks: total_blocks as u32,
+        }
+    }
+
+    /// Build the next [`Block`] for `chunk` without writing it, advancing
+    /// the internal block counter.
+    ///
+    /// Exposed so callers can attach extensions or a checksum (e.g. via
+    /// [`Block::push_extension`]) before handing the block to
+    /// [`write_block`](Self::write_block). `chunk` must be no longer than
+    /// the configured payload size. Returns [`BlockError::AddressOverflow`]
+    /// if the block's `target_addr` would not fit in a `u32`.
+    pub fn make_block(&mut self, chunk: &[u8]) -> Result<Block, BlockError> {
+        if chunk.len() > self.payload_size {
+            return Err(BlockError::PayloadSize);
+        }
+
+        let offset = (self.block as usize)
+            .checked_mul(self.payload_size)
+            .ok_or(BlockError::AddressOverflow)?;
+        let target_addr = (self.target_addr as usize)
+            .checked_add(offset)
+            .filter(|addr| *addr <= u32::MAX as usize)
+            .ok_or(BlockError::AddressOverflow)?;
+
+        let mut block = Block::new(
+            self.block as usize,
+            self.total_blocks as usize,
+            chunk,
+            target_addr,
+        );
+        block.data_len = chunk.len() as u32;
+
+        if let Some(family_id) = self.family_id {
+            block.board_family_id_or_file_size = family_id;
+            block.flags |= Flags::FamilyId;
+        }
+
+        self.block += 1;
+
+        Ok(block)
+    }
+
+    /// Write an already-built block to the underlying writer.
+    pub fn write_block(&mut self, block: &Block) -> Result<(), BlockError> {
+        self.writer
+            .write_all(block.as_bytes())
+            .map_err(|_| BlockError::Io)
+    }
+
+    /// Write the next chunk of firmware data as a single block.
+    ///
+    /// `chunk` must be no longer than the configured payload size.
+    pub fn write_chunk(&mut self, chunk: &[u8]) -> Result<(), BlockError> {
+        let block = self.make_block(chunk)?;
+        self.write_block(&block)
+    }
+
+    /// Returns `true` if the next block built by
+    /// [`make_block`](Self::make_block) will be the last one.
+    pub fn is_last_chunk(&self) -> bool {
+        self.block + 1 >= self.total_blocks
+    }
+
+    /// Configured payload bytes per block.
+    pub fn payload_size(&self) -> usize {
+        self.payload_size
+    }
+
+    /// Total number of blocks this image will contain.
+    pub fn total_blocks(&self) -> u32 {
+        self.total_blocks
+    }
+
+    /// Flush the underlying writer and return it.
+    pub fn finish(mut self) -> Result<W, BlockError> {
+        self.writer.flush().map_err(|_| BlockError::Io)?;
+        Ok(self.writer)
+    }
+}
+
+/// Lazily reads and validates [`Block`]s from a UF2 image.
+pub struct Uf2Reader<R> {
+    reader: R,
+}
+
+impl<R: Read> Uf2Reader<R> {
+    /// Create a reader over `reader`.
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: Read> Iterator for Uf2Reader<R> {
+    type Item = Result<Block, BlockError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = [0u8; 512];
+
+        match self.reader.read_exact(&mut buf) {
+            Ok(()) => Some(Block::from_bytes(&buf)),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
+            Err(_) => Some(Err(BlockError::Io)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let firmware = b"some firmware bytes, more than one block long!!";
+
+        let mut output = Vec::new();
+        let mut writer = Uf2Writer::with_payload_size(
+            &mut output,
+            0x1000,
+            Some(0xe48bff56),
+            firmware.len(),
+            16,
+        );
+
+        for chunk in firmware.chunks(16) {
+            writer.write_chunk(chunk).unwrap();
+        }
+
+        writer.finish().unwrap();
+
+        let blocks: Vec<Block> = Uf2Reader::new(output.as_slice())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(blocks.len(), firmware.len().div_ceil(16));
+
+        let rebuilt: Vec<u8> = blocks
+            .iter()
+            .flat_map(|b| b.data[0..b.data_len as usize].to_vec())
+            .collect();
+
+        assert_eq!(rebuilt, firmware);
+    }
+}
@@ -1,8 +1,21 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
 use core::{fmt, mem::size_of};
 use zerocopy::{AsBytes, FromBytes, FromZeroes};
 
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+pub use io::{Uf2Reader, Uf2Writer};
+
+#[cfg(feature = "std")]
+mod container;
+#[cfg(feature = "std")]
+pub use container::{read_container, write_container, ContainerFile, ExtractedFile};
+
+mod family_id;
+pub use family_id::FamilyId;
+
 const MAX_PAYLOAD_SIZE: usize = 476;
 
 /// Magic numbers.
@@ -18,6 +31,11 @@ pub enum BlockError {
     MagicNumber,
     /// Payload size too large.
     PayloadSize,
+    /// A computed `target_addr` does not fit in a `u32`.
+    AddressOverflow,
+    /// An I/O error occurred while reading or writing a block.
+    #[cfg(feature = "std")]
+    Io,
 }
 
 impl fmt::Display for BlockError {
@@ -26,10 +44,16 @@ impl fmt::Display for BlockError {
             Self::InputBuffer => write!(f, "Input buffer"),
             Self::MagicNumber => write!(f, "Magic number incorrect"),
             Self::PayloadSize => write!(f, "Payload size too large"),
+            Self::AddressOverflow => write!(f, "Target address overflow"),
+            #[cfg(feature = "std")]
+            Self::Io => write!(f, "I/O error"),
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for BlockError {}
+
 /// Block structure.
 ///
 /// Length is fixed at 512 bytes with a variable size data section up to 476 bytes.
@@ -157,6 +181,54 @@ impl Block {
         self.data[begin..end].copy_from_slice(checksum.as_bytes())
     }
 
+    /// Compute the MD5 checksum over the block's payload and write it into
+    /// the last 24 bytes of `data`, setting [`Flags::Checksum`].
+    ///
+    /// The covered region is `data[0..data_len]`, addressed from
+    /// `target_addr`. Returns [`BlockError::PayloadSize`] if `data_len`
+    /// reaches into the last 24 bytes, which would otherwise overwrite
+    /// payload data with the checksum computed over it.
+    #[cfg(feature = "md5")]
+    pub fn fill_checksum(&mut self) -> Result<(), BlockError> {
+        let region_end = self.data.len() - size_of::<Checksum>();
+
+        if self.data_len as usize > region_end {
+            return Err(BlockError::PayloadSize);
+        }
+
+        let checksum = compute_checksum(
+            &self.data[0..self.data_len as usize],
+            self.target_addr,
+        );
+
+        self.set_checksum(checksum);
+        self.flags |= Flags::Checksum;
+
+        Ok(())
+    }
+
+    /// Recompute the MD5 checksum of the covered region and compare it
+    /// against the embedded [`Checksum`].
+    ///
+    /// Returns `false` if the checksum flag is not set.
+    #[cfg(feature = "md5")]
+    pub fn verify_checksum(&self) -> bool {
+        let Some(checksum) = self.checksum() else {
+            return false;
+        };
+
+        let start = checksum.start;
+        let length = checksum.length as usize;
+
+        if start != self.target_addr || length > self.data_len as usize {
+            return false;
+        }
+
+        let expected = compute_checksum(&self.data[0..length], start);
+
+        expected.checksum == checksum.checksum
+    }
+
     /// Returns `true` if the extensions flag is set.
     pub fn has_extensions(&self) -> bool {
         self.flags.contains(Flags::ExtensionTags)
@@ -173,6 +245,66 @@ impl Block {
             None
         }
     }
+
+    /// Append an extension TLV after any existing extensions.
+    ///
+    /// The payload is written with a 1-byte length + 3-byte little-endian
+    /// tag header, padded to the 4-byte [`Extensions::ALIGN`] boundary, and
+    /// [`Flags::ExtensionTags`] is set. Returns [`BlockError::PayloadSize`]
+    /// if `data` is too long to fit a single TLV, or if appending it would
+    /// overflow the payload region (accounting for any reserved checksum
+    /// bytes).
+    pub fn push_extension(
+        &mut self,
+        tag: u32,
+        data: &[u8],
+    ) -> Result<(), BlockError> {
+        if data.len() > 255 - Extensions::HEADER_SIZE {
+            return Err(BlockError::PayloadSize);
+        }
+
+        let region_end = if self.has_checksum() {
+            self.data.len() - size_of::<Checksum>()
+        } else {
+            self.data.len()
+        };
+
+        let start = self.extensions_end();
+        let len = Extensions::HEADER_SIZE + data.len();
+        let end = start + len;
+
+        if end > region_end {
+            return Err(BlockError::PayloadSize);
+        }
+
+        self.data[start] = len as u8;
+        self.data[start + 1..start + 4]
+            .copy_from_slice(&tag.to_le_bytes()[0..3]);
+        self.data[start + 4..end].copy_from_slice(data);
+
+        let padded_end = end.next_multiple_of(Extensions::ALIGN);
+        self.data[end..padded_end].fill(0);
+
+        self.flags |= Flags::ExtensionTags;
+
+        Ok(())
+    }
+
+    /// Offset immediately after the last valid extension TLV, or the start
+    /// of the extensions region if none have been written yet.
+    fn extensions_end(&self) -> usize {
+        let base =
+            (self.data_len as usize).next_multiple_of(Extensions::ALIGN);
+
+        if !self.has_extensions() {
+            return base;
+        }
+
+        let mut iter = Extensions::from_bytes(&self.data[base..]);
+        while iter.next().is_some() {}
+
+        base + iter.start
+    }
 }
 
 /// Checksum information.
@@ -193,6 +325,68 @@ const _: () = {
     assert!(core::mem::size_of::<Checksum>() == 24);
 };
 
+/// Compute the MD5 [`Checksum`] of `data`, covering `length` bytes starting
+/// at flash address `start`.
+#[cfg(feature = "md5")]
+pub fn compute_checksum(data: &[u8], start: u32) -> Checksum {
+    use md5::{Digest, Md5};
+
+    let digest = Md5::digest(data);
+
+    Checksum {
+        start,
+        length: data.len() as u32,
+        checksum: digest.into(),
+    }
+}
+
+/// Compute the SHA-256 digest of a whole firmware image, as embedded by the
+/// [`ExtensionTag::Sha2Checksum`] extension.
+///
+/// `binary` is the reconstructed firmware, i.e. the concatenation of every
+/// block's `data[0..data_len]` in address order.
+#[cfg(feature = "sha2")]
+pub fn compute_firmware_digest(binary: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(binary).into()
+}
+
+/// Recompute the SHA-256 digest of `blocks` and compare it against the
+/// embedded [`ExtensionTag::Sha2Checksum`] extension.
+///
+/// `blocks` are hashed in `target_addr` order, not read order, matching
+/// [`compute_firmware_digest`]'s address-order contract; UF2 images (e.g.
+/// multi-file containers) are not guaranteed to store blocks in ascending
+/// address order. Returns `None` if no block carries the extension.
+#[cfg(feature = "sha2")]
+pub fn verify_firmware_digest(blocks: &[Block]) -> Option<bool> {
+    use sha2::{Digest, Sha256};
+
+    let mut sorted: Vec<&Block> = blocks.iter().collect();
+    sorted.sort_by_key(|b| b.target_addr);
+
+    let mut hasher = Sha256::new();
+    let mut embedded: Option<[u8; 32]> = None;
+
+    for block in sorted {
+        hasher.update(&block.data[0..block.data_len as usize]);
+
+        if let Some(extensions) = block.extensions() {
+            for extension in extensions {
+                if extension.tag == ExtensionTag::Sha2Checksum {
+                    embedded = extension.data.try_into().ok();
+                }
+            }
+        }
+    }
+
+    let embedded = embedded?;
+    let digest: [u8; 32] = hasher.finalize().into();
+
+    Some(embedded == digest)
+}
+
 /// Block flags.
 #[derive(
     Debug, Default, Clone, Copy, PartialEq, Eq, AsBytes, FromBytes, FromZeroes,
@@ -304,6 +498,11 @@ pub enum ExtensionTag {
     Sha2Checksum = 0xb46db0,
     /// Device type identifier.
     DeviceTypeId = 0xc8a729,
+    /// UTF-8 file path, used to name a file packed into a
+    /// [`Flags::FileContainer`] image.
+    ///
+    /// Crate-local; not part of the upstream UF2 extension tag registry.
+    FilePath = 0x6a462c,
     /// Other unknown tag.
     Other(u32),
 }
@@ -316,11 +515,26 @@ impl From<u32> for ExtensionTag {
             0x0be9f7 => Self::TagetPageSize,
             0xb46db0 => Self::Sha2Checksum,
             0xc8a729 => Self::DeviceTypeId,
+            0x6a462c => Self::FilePath,
             _ => Self::Other(value), // still valid, just unknown to us
         }
     }
 }
 
+impl From<ExtensionTag> for u32 {
+    fn from(tag: ExtensionTag) -> Self {
+        match tag {
+            ExtensionTag::SemverString => 0x9fc7bc,
+            ExtensionTag::DescriptionString => 0x650d9d,
+            ExtensionTag::TagetPageSize => 0x0be9f7,
+            ExtensionTag::Sha2Checksum => 0xb46db0,
+            ExtensionTag::DeviceTypeId => 0xc8a729,
+            ExtensionTag::FilePath => 0x6a462c,
+            ExtensionTag::Other(value) => value,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -342,6 +556,29 @@ mod tests {
         assert!(cksm.is_some());
     }
 
+    #[cfg(feature = "md5")]
+    #[test]
+    fn block_checksum_roundtrip() {
+        let mut block = Block::new(0, 1, b"hello world", 0x1000);
+        block.data_len = 11;
+
+        block.fill_checksum().unwrap();
+        assert!(block.has_checksum());
+        assert!(block.verify_checksum());
+
+        block.data[0] = b'H';
+        assert!(!block.verify_checksum());
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn block_fill_checksum_overflow() {
+        let mut block = Block::new(0, 1, b"hello world", 0x1000);
+        block.data_len = 453;
+
+        assert_eq!(block.fill_checksum(), Err(BlockError::PayloadSize));
+    }
+
     #[test]
     fn block_extension() {
         let mut block = Block {
@@ -383,6 +620,63 @@ mod tests {
         assert_eq!(third.data, b"ACME Toaster mk3");
     }
 
+    #[test]
+    fn block_push_extension() {
+        let mut block = Block::default();
+
+        block
+            .push_extension(u32::from(ExtensionTag::SemverString), b"1.2.3")
+            .unwrap();
+        block
+            .push_extension(u32::from(ExtensionTag::DescriptionString), b"test")
+            .unwrap();
+
+        assert!(block.has_extensions());
+
+        let mut extensions = block.extensions().unwrap();
+
+        let first = extensions.next().unwrap();
+        assert_eq!(first.tag, ExtensionTag::SemverString);
+        assert_eq!(first.data, b"1.2.3");
+
+        let second = extensions.next().unwrap();
+        assert_eq!(second.tag, ExtensionTag::DescriptionString);
+        assert_eq!(second.data, b"test");
+
+        assert!(extensions.next().is_none());
+    }
+
+    #[test]
+    fn block_push_extension_overflow() {
+        let mut block = Block::default();
+        let data = [0u8; 252];
+
+        assert_eq!(
+            block.push_extension(u32::from(ExtensionTag::DescriptionString), &data),
+            Err(BlockError::PayloadSize)
+        );
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn firmware_digest_roundtrip() {
+        let binary = b"firmware image contents";
+        let digest = compute_firmware_digest(binary);
+
+        let mut block = Block::default();
+        block
+            .push_extension(u32::from(ExtensionTag::Sha2Checksum), &digest)
+            .unwrap();
+
+        let found = block
+            .extensions()
+            .unwrap()
+            .find(|e| e.tag == ExtensionTag::Sha2Checksum)
+            .unwrap();
+
+        assert_eq!(found.data, digest);
+    }
+
     #[test]
     fn example_file() {
         use std::io::prelude::*;